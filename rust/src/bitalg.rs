@@ -17,13 +17,19 @@
 //! variables.    
 
 use ::bitmat::BitMat;
-use ::bitvec::BitVec;
+use ::bitvec::{BitVec, WORDSIZE};
 
 /// Implements Gauss algorithm for `n_rows` logical equations and `n_cols-1`
 /// variables.    
 pub struct BitGauss {
     sys: BitMat,
     rank: usize,
+    // pivot_cols[r] is the variable column that ended up pivoted into row r,
+    // in row order. Columns that never get a pivot (e.g. a column that is
+    // linearly dependent on others, reachable via an arbitrary toggle graph
+    // from `from_toggle_matrix`) are simply absent, so pivot columns are not
+    // assumed to be the contiguous range `0..rank`.
+    pivot_cols: Vec<usize>,
 }
 
 impl BitGauss {
@@ -34,6 +40,7 @@ impl BitGauss {
         BitGauss {
             sys: sys,
             rank: 0,
+            pivot_cols: Vec::new(),
         }
     }
         
@@ -57,13 +64,25 @@ impl BitGauss {
     
     /// Gausses system with `n_rows` logical equations and `n_cols-1` variables.
     ///
+    /// Internally this runs the Method of Four Russians (M4RI): columns are
+    /// eliminated in windows of `k` at a time instead of one at a time. Each
+    /// window first finds its (up to `k`) pivot rows with ordinary scalar
+    /// elimination restricted to that small local group of rows, then builds
+    /// a table of all `2^k` XOR-combinations of those pivot rows in Gray-code
+    /// order so every other row can be cleared of the whole window in a
+    /// single word-parallel row XOR instead of `k` separate ones. This keeps
+    /// the result and `rank()` bookkeeping identical to plain scalar
+    /// elimination while dropping the overall cost from `O(n^3)` towards
+    /// `O(n^3 / log n)`.
+    ///
     /// # Examples
     ///
     /// ```
     /// use los::bitalg::BitGauss;
+    /// use los::bitmat::BitMat;
     ///
     /// let n = 3;
-    /// let mut alg = BitGauss::with_size(n, n+1);
+    /// let mut alg = BitGauss::with(BitMat::with_size(n, n+1));
     ///
     /// for i in 0..n {
     ///     alg.sys_mut().set(i, n-i-1, true);
@@ -71,36 +90,145 @@ impl BitGauss {
     /// }
     ///
     /// assert_eq!(alg.sys().to_string(), "0011\n0101\n1001\n");
-    /// alg.gauss();    
+    /// alg.gauss();
     /// assert_eq!(alg.sys().to_string(), "1001\n0101\n0011\n");
     /// assert_eq!(alg.rank(), n);
     /// ```
     pub fn gauss(&mut self) {
         let n_rows = self.sys.n_rows();
-      
-        // Convert the left square matrix to a identity matrix
-        for i in 0..n_rows {
-            // Find and set one on the main diagonal
-            for j in i..n_rows {
-                if self.sys.get(j, i) {
-                    self.sys.swap(j, i);
+        self.rank = 0;
+        self.pivot_cols.clear();
+
+        let k = m4ri_window_size(n_rows);
+        let mut col = 0;
+
+        while col < n_rows {
+            let window = k.min(n_rows - col);
+            let (pivot_rows, pivot_cols) = self.eliminate_window(col, window);
+
+            if !pivot_rows.is_empty() {
+                self.clear_window_with_table(&pivot_rows, &pivot_cols);
+            }
+
+            self.pivot_cols.extend_from_slice(&pivot_cols);
+            col += window;
+        }
+    }
+
+    // Runs ordinary scalar elimination restricted to the `window` columns
+    // starting at `col`, confined to the small local group of rows that
+    // become pivots. A found pivot is always swapped into row `self.rank`,
+    // the next free row -- like `ModGauss`, never assuming a pivot for
+    // column `c` lands on row `c`, since a linearly dependent column (now
+    // reachable via an arbitrary toggle graph from `from_toggle_matrix`)
+    // can be skipped without a pivot, which would otherwise leave gaps in
+    // the row indexing. Returns the absolute row indices of the (up to
+    // `window`) pivots found alongside the variable column each one
+    // pivoted, in establishment order. Columns with no available pivot are
+    // left untouched, same as a plain scalar fallback would do.
+    fn eliminate_window(&mut self, col: usize, window: usize) -> (Vec<usize>, Vec<usize>) {
+        let n_rows = self.sys.n_rows();
+        let mut pivot_rows: Vec<usize> = Vec::with_capacity(window);
+        let mut pivot_cols: Vec<usize> = Vec::with_capacity(window);
+
+        for c in col..col + window {
+            let row = self.rank;
+
+            // Find a pivot for column `c` among the free rows `row..n_rows`.
+            // Each candidate is reduced against the pivots already
+            // established this window before its bit at `c` is trusted: the
+            // candidate hasn't had the window's earlier columns cleared
+            // from it yet (that full-matrix clear is deferred to
+            // `clear_window_with_table`), so checking the raw bit could
+            // accept a row that cancels out once reduced, or reject one
+            // that only becomes a pivot after reduction. The established
+            // pivot rows are themselves already mutually clean (by
+            // induction on this same loop), so reducing the candidate by
+            // them can only ever clear the candidate's own bits, never
+            // contaminate the established rows.
+            let mut found = None;
+            for j in row..n_rows {
+                for (t, &prev_col) in pivot_cols.iter().enumerate() {
+                    if self.sys.get(j, prev_col) {
+                        self.sys.xor(j, pivot_rows[t]);
+                    }
+                }
+
+                if self.sys.get(j, c) {
+                    found = Some(j);
+                    break;
                 }
             }
 
-            // Skip column, if it does not contains `true`
-            if !self.sys.get(i, i) {
-                continue;
+            let prow = match found {
+                Some(r) => r,
+                None => continue,
+            };
+
+            self.sys.swap(prow, row);
+
+            // Row `row` is now already clean at every established column
+            // (the reduction above ran before the swap), so using it to
+            // clear column `c` from the established pivot rows can't
+            // reintroduce a bit anywhere else in them.
+            for &prev_row in &pivot_rows {
+                if self.sys.get(prev_row, c) {
+                    self.sys.xor(prev_row, row);
+                }
             }
 
-            // Increase rank: the column contains `true` on the main diagonal
-            self.rank = i+1;
+            pivot_rows.push(row);
+            pivot_cols.push(c);
+            self.rank = row + 1;
+        }
+
+        (pivot_rows, pivot_cols)
+    }
 
-            // Set values to `false` at column except the main diagonal
-            for j in 0..n_rows {
-                if self.sys.get(j, i) && j != i {
-                    self.sys.xor(j, i);
+    // Builds the Gray-code table of all `2^pivot_rows.len()` XOR-combinations
+    // of the window's pivot rows, then clears the window from every other row
+    // in the system with one word-parallel row XOR apiece.
+    fn clear_window_with_table(&mut self, pivot_rows: &[usize], pivot_cols: &[usize]) {
+        let n_rows = self.sys.n_rows();
+        let n_cols = self.sys.n_cols();
+        let p = pivot_rows.len();
+        let n_entries = 1usize << p;
+
+        let mut table: Vec<BitVec> = Vec::with_capacity(n_entries);
+        table.push(BitVec::with_length(n_cols));
+
+        for i in 1..n_entries {
+            let gray = i ^ (i >> 1);
+            let gray_prev = (i - 1) ^ ((i - 1) >> 1);
+            let bit = (gray ^ gray_prev).trailing_zeros() as usize;
+
+            let mut entry = table[gray_prev].clone();
+            xor_row_into(&mut entry, &self.sys[pivot_rows[bit]]);
+            // `table` is indexed by Gray code, so grow it to fit `gray`.
+            if gray >= table.len() {
+                table.resize(gray + 1, BitVec::with_length(n_cols));
+            }
+            table[gray] = entry;
+        }
+
+        let is_pivot_row = |r: usize| pivot_rows.contains(&r);
+
+        for r in 0..n_rows {
+            if is_pivot_row(r) {
+                continue;
+            }
+
+            let mut idx = 0usize;
+            for (bit, &c) in pivot_cols.iter().enumerate() {
+                if self.sys.get(r, c) {
+                    idx |= 1 << bit;
                 }
             }
+
+            if idx != 0 {
+                let entry = table[idx].clone();
+                xor_row_into_mat(&mut self.sys, r, &entry);
+            }
         }
     }
 
@@ -126,52 +254,75 @@ impl BitGauss {
 
         // The system has one solution
         if rank == n_vars {
-            for i in 0..n_rows {
-                solution.set(i, self.sys.get(i, n_vars));
+            for j in 0..rank {
+                solution.set(self.pivot_cols[j], self.sys.get(j, n_vars));
             }
         }
-        // The system has 2^(n_vars-rank) solutions
+        // The system has 2^(n_vars-rank) solutions. Free variables are
+        // exactly the columns not in `pivot_cols` -- not necessarily the
+        // contiguous tail `rank..n_vars`, since a column that's linearly
+        // dependent on others can be skipped before a later column still
+        // gets a pivot. `n_rest` is represented and enumerated entirely
+        // through `BitVec`s, so it is not bounded by the machine word width
+        // like a plain `usize` subset index would be.
         else {
-            let n_rest = n_vars - rank;
-            let n_solutions = 1usize << n_rest;
-            let mut min_weight = n_cols as u32;
-            let mut weight;
+            let pivot_cols = &self.pivot_cols;
+            let free_cols: Vec<usize> = (0..n_vars).filter(|c| !pivot_cols.contains(c)).collect();
+            let n_rest = free_cols.len();
+
+            // `rest` holds the free-variable assignment for the subset being
+            // considered, walked in reflected binary Gray-code order via
+            // `counter`, an ordinary binary counter of the same width.
+            // Consecutive subsets differ in exactly one free variable, so
+            // `accumulator` and the running weight are updated in place
+            // instead of being recomputed from scratch every time.
             let mut rest = BitVec::with_length(n_rest);
-            let mut accumulator = BitVec::with_length(n_rows);
+            let mut counter = BitVec::with_length(n_rest);
+            let mut accumulator = BitVec::with_length(rank);
+
+            // Seed the accumulator with the particular solution (rest = 0)
+            for j in 0..rank {
+                accumulator.set(j, self.sys.get(j, n_vars));
+            }
 
-            // Find a solution with a minimum number of ones
-            for i in 0..n_solutions {
-                // Create subset by index i
-                rest.buf_mut()[0] = i;
-                
-                // Reset accumulator
-                accumulator.setall(false);
+            let mut rest_weight = 0u32;
+            let mut acc_weight = accumulator.count_ones();
+            let mut min_weight = rest_weight + acc_weight;
+
+            for j in 0..rank {
+                solution.set(pivot_cols[j], accumulator.get(j));
+            }
+            for (idx, &f) in free_cols.iter().enumerate() {
+                solution.set(f, rest.get(idx));
+            }
 
-                // Accumulate the solution with index i
+            // Walk every other subset, one Gray-code step at a time
+            while let Some(bit) = bitvec_increment(&mut counter) {
+                let value = !rest.get(bit);
+                rest.set(bit, value);
+                rest_weight = if value { rest_weight + 1 } else { rest_weight - 1 };
+
+                // XOR free column `free_cols[bit]` of the reduced system
+                // into the accumulator, keeping its Hamming weight up to date
+                let col = free_cols[bit];
                 for j in 0..rank {
-                    for k in 0..n_rest {
-                        if rest.get(k) {
-                            accumulator.xor(j, self.sys.get(j, rank + k));
-                        }
+                    if self.sys.get(j, col) {
+                        let new_bit = !accumulator.get(j);
+                        accumulator.set(j, new_bit);
+                        acc_weight = if new_bit { acc_weight + 1 } else { acc_weight - 1 };
                     }
-                    accumulator.xor(j, self.sys.get(j, n_vars));
                 }
 
-                // Weigh out the solution with index i
-                weight = rest.count_ones() + accumulator.count_ones();
+                let weight = rest_weight + acc_weight;
 
-                // Build the solution with less weight
                 if weight < min_weight {
                     min_weight = weight;
 
-                    // Get first part of elemets of solution from accumulator
                     for j in 0..rank {
-                        solution.set(j, accumulator.get(j));
+                        solution.set(pivot_cols[j], accumulator.get(j));
                     }
-
-                    // Get second part of elemets of solution from rest
-                    for j in 0..n_rest {
-                        solution.set(rank + j, rest.get(j));
+                    for (idx, &f) in free_cols.iter().enumerate() {
+                        solution.set(f, rest.get(idx));
                     }
                 }
             }
@@ -179,4 +330,167 @@ impl BitGauss {
 
         Some(solution)
     }
+
+    /// Gausses the system and returns its full affine solution space: a
+    /// particular solution plus a basis of the null space, one `BitVec` per
+    /// free variable. Returns `None` if the system is inconsistent.
+    ///
+    /// Every solution of the system is the particular solution XOR some
+    /// subset of the basis vectors, so walking all `2^(n_vars-rank)` subsets
+    /// (e.g. in Gray-code order) enumerates every valid assignment, not just
+    /// the minimum-weight one that `solve` returns.
+    pub fn solution_space(&mut self) -> Option<(BitVec, Vec<BitVec>)> {
+        self.gauss();
+
+        let n_rows = self.sys.n_rows();
+        let n_cols = self.sys.n_cols();
+        let n_vars = n_cols - 1;
+        let rank = self.rank;
+
+        for i in rank..n_rows {
+            if self.sys.get(i, n_vars) {
+                return None;
+            }
+        }
+
+        // Free variables are exactly the columns not in `pivot_cols` -- not
+        // necessarily the contiguous tail `rank..n_vars`, since a column
+        // that's linearly dependent on others can be skipped before a later
+        // column still gets a pivot.
+        let pivot_cols = &self.pivot_cols;
+        let free_cols: Vec<usize> = (0..n_vars).filter(|c| !pivot_cols.contains(c)).collect();
+
+        let mut particular = BitVec::with_length(n_vars);
+        for j in 0..rank {
+            particular.set(pivot_cols[j], self.sys.get(j, n_vars));
+        }
+
+        let mut basis = Vec::with_capacity(free_cols.len());
+
+        for &f in &free_cols {
+            let mut vector = BitVec::with_length(n_vars);
+            for j in 0..rank {
+                vector.set(pivot_cols[j], self.sys.get(j, f));
+            }
+            vector.set(f, true);
+            basis.push(vector);
+        }
+
+        Some((particular, basis))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Minimum-weight search over an affine solution space
+////////////////////////////////////////////////////////////////////////////////
+
+// Caps the Gray-code walk at 2^(WORDSIZE-1) steps so `1usize << k` never
+// overflows the subset index.
+const MAX_KERNEL_WALK: usize = WORDSIZE - 1;
+
+/// Finds the minimum-weight vector in the affine solution space described
+/// by `particular` and a null-space `basis`, as returned by
+/// `BitGauss::solution_space`. Every vector in the space is `particular`
+/// XOR some subset of `basis`, so this walks all `2^basis.len()` subsets in
+/// Gray-code order -- stepping the subset index `i` from `1` to
+/// `2^basis.len() - 1` and XOR-ing in `basis[i.trailing_zeros()]` each
+/// time, since that is exactly the basis vector whose membership flips
+/// between consecutive Gray codes -- and keeps the lightest vector seen.
+///
+/// `basis.len()` is capped at `WORDSIZE - 1` so the subset index stays a
+/// plain `usize`; a nullity that large makes an exhaustive walk infeasible
+/// anyway, so callers needing an unbounded nullity should use
+/// `BitGauss::solve` instead, which drives the same walk with an
+/// arbitrary-width `BitVec` counter.
+///
+/// # Examples
+///
+/// ```
+/// use los::bitvec::BitVec;
+/// use los::bitalg::min_weight_solution;
+///
+/// let particular = BitVec::from("11");
+/// let basis = vec![BitVec::from("10")];
+/// // particular (weight 2) XOR basis[0] = "01" (weight 1) is lighter.
+/// assert_eq!(min_weight_solution(&particular, &basis).to_string(), "01");
+/// ```
+pub fn min_weight_solution(particular: &BitVec, basis: &[BitVec]) -> BitVec {
+    let k = basis.len().min(MAX_KERNEL_WALK);
+    let n_entries = 1usize << k;
+
+    let mut current = particular.clone();
+    let mut best = current.clone();
+    let mut best_weight = best.count_ones();
+
+    for i in 1..n_entries {
+        let bit = i.trailing_zeros() as usize;
+        current.xor_assign(&basis[bit]);
+
+        let weight = current.count_ones();
+        if weight < best_weight {
+            best_weight = weight;
+            best = current.clone();
+        }
+    }
+
+    best
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions for the M4RI block elimination
+////////////////////////////////////////////////////////////////////////////////
+
+// Caps the Gray-code table at 2^16 rows so it never dominates memory use.
+const M4RI_MAX_WINDOW: usize = 16;
+
+// Picks the M4RI window size k ~= floor(log2(n_rows)), capped so the
+// 2^k-entry Gray-code table stays small.
+#[inline]
+fn m4ri_window_size(n_rows: usize) -> usize {
+    if n_rows <= 1 {
+        return 1;
+    }
+
+    let k = (::std::mem::size_of::<usize>() * 8) - (n_rows.leading_zeros() as usize) - 1;
+    k.max(1).min(M4RI_MAX_WINDOW)
+}
+
+// XORs `row` into `dest`; both are full system rows of the same length.
+#[inline]
+pub(crate) fn xor_row_into(dest: &mut BitVec, row: &BitVec) {
+    dest.xor_assign(row);
+}
+
+// XORs `row` into row `r` of `mat` word by word.
+#[inline]
+fn xor_row_into_mat(mat: &mut BitMat, r: usize, row: &BitVec) {
+    let len = mat[r].buf().len();
+    for i in 0..len {
+        mat[r].buf_mut()[i] ^= row.buf()[i];
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper function for incremental Gray-code subset enumeration
+////////////////////////////////////////////////////////////////////////////////
+
+// Increments the arbitrary-width binary counter `v` by one, in place.
+// Returns the index of the bit that flipped from `0` to `1` (every lower bit
+// ripples from `1` to `0`), which is exactly the bit that flips between
+// consecutive reflected binary Gray codes. Returns `None` once the counter
+// has wrapped back around to all zeroes, i.e. every subset has been visited.
+#[inline]
+pub(crate) fn bitvec_increment(v: &mut BitVec) -> Option<usize> {
+    let len = v.len();
+
+    for i in 0..len {
+        if v.get(i) {
+            v.set(i, false);
+        } else {
+            v.set(i, true);
+            return Some(i);
+        }
+    }
+
+    None
 }
@@ -19,7 +19,7 @@
 use std::ptr;
 use std::fmt;
 use std::ops::{Index, IndexMut};
-use ::bitvec::BitVec;
+use ::bitvec::{BitVec, WORDSIZE};
 
 /// A contiguous growable boolean matrix type with heap-allocated contents.
 /// The rows are represented by a vector of references to `BitVec`.
@@ -193,6 +193,162 @@ impl BitMat {
     pub fn get(&self, row: usize, col: usize) -> bool {
         self.rows()[row].get(col)
     }
+
+    /// Returns column `col` as a `BitVec` of length `n_rows`.
+    ///
+    /// This walks every row once, so it costs `O(n_rows)`; when more than a
+    /// handful of columns are needed, `transpose()` and then indexing the
+    /// rows of the result is cheaper overall.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitmat::BitMat;
+    ///
+    /// let mut mat = BitMat::with_size(2, 2);
+    /// mat.set(0, 1, true);
+    /// assert_eq!(mat.col(1).to_string(), "10");
+    /// ```
+    pub fn col(&self, col: usize) -> BitVec {
+        let mut result = BitVec::with_length(self.n_rows());
+
+        for (i, row) in self.rows().iter().enumerate() {
+            if row.get(col) {
+                result.set(i, true);
+            }
+        }
+
+        result
+    }
+
+    /// Returns the transpose of this matrix: an `n_cols` x `n_rows` matrix
+    /// where `result.get(j, i) == self.get(i, j)`.
+    ///
+    /// Transposing bit-by-bit is cache-unfriendly, since it strides across
+    /// every row for a single output word. Instead this works in
+    /// `WORDSIZE x WORDSIZE` tiles: each tile is transposed in place with the
+    /// standard word-parallel bit-swap kernel (`log2(WORDSIZE)` rounds of
+    /// masked XORs), so the whole matrix only costs `O(n_rows * n_cols / WORDSIZE)`
+    /// word operations rather than one branch per bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitmat::BitMat;
+    ///
+    /// let mut mat = BitMat::with_size(2, 3);
+    /// mat.set(0, 2, true);
+    /// mat.set(1, 0, true);
+    ///
+    /// let t = mat.transpose();
+    /// assert_eq!(t.n_rows(), 3);
+    /// assert_eq!(t.n_cols(), 2);
+    /// assert_eq!(t.get(2, 0), true);
+    /// assert_eq!(t.get(0, 1), true);
+    /// ```
+    pub fn transpose(&self) -> BitMat {
+        let n_rows = self.n_rows();
+        let n_cols = self.n_cols();
+        let mut result = BitMat::with_size(n_cols, n_rows);
+
+        let mut tile = [0usize; WORDSIZE];
+        let mut row_block = 0;
+
+        while row_block < n_rows {
+            let mut col_block = 0;
+
+            while col_block < n_cols {
+                for i in 0..WORDSIZE {
+                    let r = row_block + i;
+                    tile[i] = if r < n_rows {
+                        word_at(&self.rows[r], col_block)
+                    } else {
+                        0
+                    };
+                }
+
+                transpose_tile(&mut tile);
+
+                for (i, &word) in tile.iter().enumerate() {
+                    let c = col_block + i;
+                    if c < n_cols {
+                        set_word_at(&mut result.rows[c], row_block, word);
+                    }
+                }
+
+                col_block += WORDSIZE;
+            }
+
+            row_block += WORDSIZE;
+        }
+
+        result
+    }
+}
+
+// Reads the word of `row` starting at the word-aligned bit offset `word_col`,
+// or `0` if `row` has no buffer word there. Bits of the result beyond
+// `row.len()` may be stray tail bits (e.g. after `setall(true)`); `transpose`
+// only moves those into output rows past `n_cols`, which it discards.
+#[inline]
+fn word_at(row: &BitVec, word_col: usize) -> usize {
+    let idx = word_col / WORDSIZE;
+    match row.buf().get(idx) {
+        Some(&word) => word,
+        None => 0,
+    }
+}
+
+// Writes `value` into the buffer word of `row` starting at the word-aligned
+// bit offset `word_row`. Does nothing if `row` has no buffer word there
+// (i.e. `word_row` is past `row`'s length).
+#[inline]
+fn set_word_at(row: &mut BitVec, word_row: usize, value: usize) {
+    let idx = word_row / WORDSIZE;
+    if idx < row.buf().len() {
+        row.buf_mut()[idx] = value;
+    }
+}
+
+// Transposes a `WORDSIZE x WORDSIZE` bit matrix in place, where `tile[i]`
+// bit `j` holds row `i`, column `j`. This is the classic word-parallel
+// transpose: at each round half of the remaining block width is swapped
+// between pairs of rows `j` apart using a mask of alternating `j`-bit groups,
+// halving `j` every round until every bit has found its transposed home.
+fn transpose_tile(tile: &mut [usize; WORDSIZE]) {
+    let mut j = WORDSIZE / 2;
+
+    while j != 0 {
+        let m = swap_mask(j);
+        let mut k = 0;
+
+        while k < WORDSIZE {
+            for i in k..k + j {
+                let t = (tile[i + j] ^ (tile[i] >> j)) & m;
+                tile[i + j] ^= t;
+                tile[i] ^= t << j;
+            }
+
+            k += j * 2;
+        }
+
+        j /= 2;
+    }
+}
+
+// Builds the mask used by `transpose_tile` for block width `j`: `j` one
+// bits followed by `j` zero bits, repeated across the whole word.
+#[inline]
+fn swap_mask(j: usize) -> usize {
+    let mut mask = (1usize << j) - 1;
+    let mut shift = j * 2;
+
+    while shift < WORDSIZE {
+        mask |= mask << shift;
+        shift *= 2;
+    }
+
+    mask
 }
 
 ////////////////////////////////////////////////////////////////////////////////
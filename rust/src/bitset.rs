@@ -0,0 +1,261 @@
+// Copyright (C) 2017 - Pavel Nikitin
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! A set of `usize` elements backed by a `BitVec`, one bit per possible
+//! element. The backing vector grows on demand as larger elements are
+//! inserted, so a `BitSet` never needs an upper bound declared up front.
+
+use ::bitvec::{BitVec, Ones};
+
+/// A set of `usize` elements backed by a `BitVec`.
+pub struct BitSet {
+    bits: BitVec,
+}
+
+impl BitSet {
+    /// Constructs a new, empty `BitSet`.
+    #[inline]
+    pub fn new() -> BitSet {
+        BitSet {
+            bits: BitVec::new(),
+        }
+    }
+
+    /// Returns the number of elements in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Returns whether `value` is in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitset::BitSet;
+    ///
+    /// let mut set = BitSet::new();
+    /// set.insert(3);
+    /// assert_eq!(set.contains(3), true);
+    /// assert_eq!(set.contains(4), false);
+    /// ```
+    #[inline]
+    pub fn contains(&self, value: usize) -> bool {
+        value < self.bits.len() && self.bits.get(value)
+    }
+
+    /// Inserts `value` into the set, growing the backing `BitVec` if
+    /// `value` is past its current length. Returns `true` if the value was
+    /// not already present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitset::BitSet;
+    ///
+    /// let mut set = BitSet::new();
+    /// assert_eq!(set.insert(5), true);
+    /// assert_eq!(set.insert(5), false);
+    /// ```
+    pub fn insert(&mut self, value: usize) -> bool {
+        if value >= self.bits.len() {
+            self.bits.resize(value + 1);
+        }
+
+        let existed = self.bits.get(value);
+        self.bits.set(value, true);
+        !existed
+    }
+
+    /// Removes `value` from the set. Returns `true` if it was present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitset::BitSet;
+    ///
+    /// let mut set = BitSet::new();
+    /// set.insert(2);
+    /// assert_eq!(set.remove(2), true);
+    /// assert_eq!(set.remove(2), false);
+    /// ```
+    pub fn remove(&mut self, value: usize) -> bool {
+        if value >= self.bits.len() {
+            return false;
+        }
+
+        let existed = self.bits.get(value);
+        self.bits.set(value, false);
+        existed
+    }
+
+    /// Returns an iterator over the elements of the set, in ascending
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitset::BitSet;
+    ///
+    /// let mut set = BitSet::new();
+    /// set.insert(1);
+    /// set.insert(4);
+    /// assert_eq!(set.iter().collect::<Vec<usize>>(), vec![1, 4]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Ones {
+        self.bits.ones()
+    }
+
+    /// Returns the union of `self` and `other`: elements in either set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitset::BitSet;
+    ///
+    /// let mut a = BitSet::new();
+    /// a.insert(1);
+    /// let mut b = BitSet::new();
+    /// b.insert(2);
+    /// assert_eq!(a.union(&b).iter().collect::<Vec<usize>>(), vec![1, 2]);
+    /// ```
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        let (mut bits, shorter) = if self.bits.len() >= other.bits.len() {
+            (self.bits.clone(), &other.bits)
+        } else {
+            (other.bits.clone(), &self.bits)
+        };
+
+        let words = shorter.buf().len();
+        for i in 0..words {
+            bits.buf_mut()[i] |= shorter.buf()[i];
+        }
+
+        BitSet { bits: bits }
+    }
+
+    /// Returns the intersection of `self` and `other`: elements in both
+    /// sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitset::BitSet;
+    ///
+    /// let mut a = BitSet::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    /// let mut b = BitSet::new();
+    /// b.insert(2);
+    /// assert_eq!(a.intersection(&b).iter().collect::<Vec<usize>>(), vec![2]);
+    /// ```
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        let (mut bits, longer) = if self.bits.len() <= other.bits.len() {
+            (self.bits.clone(), &other.bits)
+        } else {
+            (other.bits.clone(), &self.bits)
+        };
+
+        let words = bits.buf().len();
+        for i in 0..words {
+            bits.buf_mut()[i] &= longer.buf()[i];
+        }
+
+        BitSet { bits: bits }
+    }
+
+    /// Returns the difference of `self` and `other`: elements in `self`
+    /// but not in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitset::BitSet;
+    ///
+    /// let mut a = BitSet::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    /// let mut b = BitSet::new();
+    /// b.insert(2);
+    /// assert_eq!(a.difference(&b).iter().collect::<Vec<usize>>(), vec![1]);
+    /// ```
+    pub fn difference(&self, other: &BitSet) -> BitSet {
+        let mut bits = self.bits.clone();
+
+        let words = other.bits.buf().len().min(bits.buf().len());
+        for i in 0..words {
+            bits.buf_mut()[i] &= !other.bits.buf()[i];
+        }
+
+        BitSet { bits: bits }
+    }
+
+    /// Returns the symmetric difference of `self` and `other`: elements in
+    /// exactly one of the two sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitset::BitSet;
+    ///
+    /// let mut a = BitSet::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    /// let mut b = BitSet::new();
+    /// b.insert(2);
+    /// b.insert(3);
+    /// assert_eq!(a.symmetric_difference(&b).iter().collect::<Vec<usize>>(), vec![1, 3]);
+    /// ```
+    pub fn symmetric_difference(&self, other: &BitSet) -> BitSet {
+        let (mut bits, shorter) = if self.bits.len() >= other.bits.len() {
+            (self.bits.clone(), &other.bits)
+        } else {
+            (other.bits.clone(), &self.bits)
+        };
+
+        let words = shorter.buf().len();
+        for i in 0..words {
+            bits.buf_mut()[i] ^= shorter.buf()[i];
+        }
+
+        BitSet { bits: bits }
+    }
+
+    /// Returns whether every element of `self` is also in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitset::BitSet;
+    ///
+    /// let mut a = BitSet::new();
+    /// a.insert(1);
+    /// let mut b = BitSet::new();
+    /// b.insert(1);
+    /// b.insert(2);
+    /// assert_eq!(a.is_subset(&b), true);
+    /// assert_eq!(b.is_subset(&a), false);
+    /// ```
+    pub fn is_subset(&self, other: &BitSet) -> bool {
+        for value in self.bits.ones() {
+            if !other.contains(value) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
@@ -21,6 +21,7 @@
 //! `O(1)` pop (from the end).
 
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// A contiguous growable boolean array type with heap-allocated contents. Each
 /// element occupies one bit. Elements are grouped into `target_pointer_width`
@@ -267,7 +268,113 @@ impl BitVec {
         }
     }
 
-    /// Appends an element to the back of a collection. Reallocation of memory 
+    /// ANDs this vector in-place with `other`, word by word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitvec::BitVec;
+    ///
+    /// let mut a = BitVec::from("1100");
+    /// a.and_assign(&BitVec::from("1010"));
+    /// assert_eq!(a.to_string(), "1000");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` has fewer `usize` words than `self`.
+    #[inline]
+    pub fn and_assign(&mut self, other: &BitVec) {
+        let len = self.buf.len();
+        for i in 0..len {
+            self.buf[i] &= other.buf[i];
+        }
+    }
+
+    /// ORs this vector in-place with `other`, word by word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitvec::BitVec;
+    ///
+    /// let mut a = BitVec::from("1100");
+    /// a.or_assign(&BitVec::from("0010"));
+    /// assert_eq!(a.to_string(), "1110");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` has fewer `usize` words than `self`.
+    #[inline]
+    pub fn or_assign(&mut self, other: &BitVec) {
+        let len = self.buf.len();
+        for i in 0..len {
+            self.buf[i] |= other.buf[i];
+        }
+    }
+
+    /// XORs this vector in-place with `other`, word by word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitvec::BitVec;
+    ///
+    /// let mut a = BitVec::from("1100");
+    /// a.xor_assign(&BitVec::from("1010"));
+    /// assert_eq!(a.to_string(), "0110");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` has fewer `usize` words than `self`.
+    #[inline]
+    pub fn xor_assign(&mut self, other: &BitVec) {
+        let len = self.buf.len();
+        for i in 0..len {
+            self.buf[i] ^= other.buf[i];
+        }
+    }
+
+    /// Flips every element in-place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitvec::BitVec;
+    ///
+    /// let mut a = BitVec::from("1100");
+    /// a.not();
+    /// assert_eq!(a.to_string(), "0011");
+    /// ```
+    pub fn not(&mut self) {
+        let buf_len = self.buf.len();
+        for i in 0..buf_len {
+            self.buf[i] = !self.buf[i];
+        }
+
+        // Flipping every word also flips the unused tail bits beyond `len`;
+        // clear them back to zero so nothing past the vector's length
+        // reads as set.
+        self.fix_tail();
+    }
+
+    // Masks off any bits at or beyond `len` in the final buffer word. Must be
+    // called whenever `len` shrinks, since the bits it used to cover may
+    // still be set and would read as set again if the vector grew back.
+    #[inline]
+    fn fix_tail(&mut self) {
+        let tail = bit_index(self.len);
+        if tail != 0 {
+            let idx = buf_index(self.len);
+            if idx < self.buf.len() {
+                self.buf[idx] &= (1usize << tail) - 1;
+            }
+        }
+    }
+
+    /// Appends an element to the back of a collection. Reallocation of memory
     /// at heap occurs when there is not enough `buf` capacity.
     ///
     /// # Examples
@@ -314,8 +421,9 @@ impl BitVec {
         } else {
             result = Some(self.get(len-1));
             self.len -= 1;
+            self.fix_tail();
         }
-        
+
         result
     }
     
@@ -392,7 +500,8 @@ impl BitVec {
     pub fn truncate(&mut self, len: usize) {
         if len < self.len {
             self.len = len;
-            self.buf.truncate(buf_capacity(len))
+            self.buf.truncate(buf_capacity(len));
+            self.fix_tail();
         }
     }
 
@@ -441,6 +550,140 @@ impl BitVec {
         count
     }
 
+    /// Returns an iterator over the indices of every set bit, in ascending
+    /// order.
+    ///
+    /// Unlike scanning `0..len()` and calling `get`, this walks `buf` one
+    /// `usize` word at a time and peels off set bits with `trailing_zeros`,
+    /// so it costs `O(capacity / WORDSIZE + count_ones())` rather than
+    /// `O(len())`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitvec::BitVec;
+    ///
+    /// let vec = BitVec::from("0101");
+    /// let indices: Vec<usize> = vec.ones().collect();
+    /// assert_eq!(indices, vec![1, 3]);
+    /// ```
+    #[inline]
+    pub fn ones(&self) -> Ones {
+        Ones {
+            buf: &self.buf,
+            len: self.len,
+            word_idx: 0,
+            cur: 0,
+        }
+    }
+
+    /// Packs this vector into bytes, 8 elements per byte, least significant
+    /// bit first, with any unused trailing bits of the last byte left at
+    /// `0`. `len()` itself is not stored; pair this with `from_bytes` and a
+    /// length tracked elsewhere, or use `to_bytes_prefixed` for a
+    /// self-describing encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitvec::BitVec;
+    ///
+    /// let vec = BitVec::from("1101");
+    /// assert_eq!(vec.to_bytes(), vec![0b1011]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let n_bytes = (self.len + 7) / 8;
+        let mut bytes = Vec::with_capacity(n_bytes);
+
+        for i in 0..n_bytes {
+            let mut byte = 0u8;
+            for b in 0..8 {
+                let index = i * 8 + b;
+                if index < self.len && self.get(index) {
+                    byte |= 1 << b;
+                }
+            }
+            bytes.push(byte);
+        }
+
+        bytes
+    }
+
+    /// Reconstructs a `BitVec` of `len` elements from bytes produced by
+    /// `to_bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitvec::BitVec;
+    ///
+    /// let vec = BitVec::from_bytes(&[0b1011], 4);
+    /// assert_eq!(vec.to_string(), "1101");
+    /// ```
+    pub fn from_bytes(bytes: &[u8], len: usize) -> BitVec {
+        let mut vec = BitVec::with_length(len);
+
+        for i in 0..len {
+            let byte = bytes[i / 8];
+            if (byte >> (i % 8)) & 1 == 1 {
+                vec.set(i, true);
+            }
+        }
+
+        vec
+    }
+
+    /// Encodes this vector as a self-describing byte string: an 8-byte
+    /// little-endian bit-length prefix followed by the packed bytes from
+    /// `to_bytes`, in the style of an SSZ bitfield.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitvec::BitVec;
+    ///
+    /// let vec = BitVec::from("1101");
+    /// let bytes = vec.to_bytes_prefixed();
+    /// assert_eq!(BitVec::from_bytes_prefixed(&bytes).unwrap().to_string(), "1101");
+    /// ```
+    pub fn to_bytes_prefixed(&self) -> Vec<u8> {
+        let len = self.len as u64;
+        let mut bytes = Vec::with_capacity(8 + (self.len + 7) / 8);
+
+        for i in 0..8 {
+            bytes.push(((len >> (i * 8)) & 0xFF) as u8);
+        }
+        bytes.extend(self.to_bytes());
+
+        bytes
+    }
+
+    /// Decodes a `BitVec` previously encoded with `to_bytes_prefixed`.
+    /// Returns an error if `bytes` is too short to hold the length prefix
+    /// or the payload it describes.
+    pub fn from_bytes_prefixed(bytes: &[u8]) -> Result<BitVec, String> {
+        if bytes.len() < 8 {
+            return Err("missing length prefix".to_string());
+        }
+
+        let mut len: u64 = 0;
+        for i in 0..8 {
+            len |= (bytes[i] as u64) << (i * 8);
+        }
+        let len = len as usize;
+
+        let payload = &bytes[8..];
+        let expected = (len + 7) / 8;
+        if payload.len() < expected {
+            return Err(format!(
+                "expected {} payload bytes for a {}-bit vector, found {}",
+                expected, len, payload.len()
+            ));
+        }
+
+        Ok(BitVec::from_bytes(payload, len))
+    }
+
     /// Converts the bit vector to string.
     ///
     /// # Examples
@@ -523,6 +766,102 @@ impl fmt::Debug for BitVec {
     }
 }
 
+/// Two vectors are equal when they have the same length and the same bits
+/// set. Compares whole words up to `buf_capacity(len())` instead of each
+/// bit, masking the final word to `len()` so stray bits past the end (e.g.
+/// left by `setall(true)`) never make equal vectors differ.
+///
+/// # Examples
+///
+/// ```
+/// use los::bitvec::BitVec;
+///
+/// assert_eq!(BitVec::from("0011"), BitVec::from("0011"));
+/// assert!(BitVec::from("0011") != BitVec::from("0010"));
+/// ```
+impl PartialEq for BitVec {
+    fn eq(&self, other: &BitVec) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        let words = buf_capacity(self.len);
+        if words == 0 {
+            return true;
+        }
+
+        let last = words - 1;
+        let mask = match bit_index(self.len) {
+            0 => usize::max_value(),
+            tail => (1usize << tail) - 1,
+        };
+        self.buf[0..last] == other.buf[0..last]
+            && (self.buf[last] & mask) == (other.buf[last] & mask)
+    }
+}
+
+impl Eq for BitVec {}
+
+/// Hashes the same masked words that `PartialEq` compares, so equal vectors
+/// always hash equally.
+impl Hash for BitVec {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        let words = buf_capacity(self.len);
+        if words == 0 {
+            return;
+        }
+
+        let last = words - 1;
+        let mask = match bit_index(self.len) {
+            0 => usize::max_value(),
+            tail => (1usize << tail) - 1,
+        };
+        self.buf[0..last].hash(state);
+        (self.buf[last] & mask).hash(state);
+    }
+}
+
+/// Iterator over the indices of every set bit of a `BitVec`, returned by
+/// `BitVec::ones`.
+pub struct Ones<'a> {
+    buf: &'a Vec<usize>,
+    len: usize,
+    word_idx: usize,
+    cur: usize,
+}
+
+impl<'a> Iterator for Ones<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let n_words = buf_capacity(self.len);
+
+        while self.cur == 0 {
+            if self.word_idx >= n_words {
+                return None;
+            }
+
+            let mut word = self.buf[self.word_idx];
+
+            // Mask off any stray bits past `len` in the final word.
+            if self.word_idx == n_words - 1 {
+                let tail = bit_index(self.len);
+                if tail != 0 {
+                    word &= (1usize << tail) - 1;
+                }
+            }
+
+            self.cur = word;
+            self.word_idx += 1;
+        }
+
+        let bit = self.cur.trailing_zeros() as usize;
+        self.cur &= self.cur - 1;
+        Some(((self.word_idx - 1) << BIT_SHIFT) + bit)
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Constants and static methods
 ////////////////////////////////////////////////////////////////////////////////
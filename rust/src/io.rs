@@ -0,0 +1,242 @@
+// Copyright (C) 2017 - Pavel Nikitin
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! A small text format for "Lights Off" puzzles, for loading a real board
+//! instead of always solving a synthetic all-on square.
+//!
+//! ```text
+//! # lines starting with "# " are comments and are skipped
+//! 3 3 k=2 wrap=0
+//! .#.
+//! ###
+//! .#.
+//! ```
+//!
+//! Grammar, informally:
+//!
+//! - An optional header line `n_rows n_cols [k=K] [wrap=0|1]`. When absent,
+//!   `n_rows` is the number of row lines and `n_cols` the length of the
+//!   first one, `k` defaults to `2` (binary) and `wrap` to `0`.
+//! - `n_rows` rows follow. For `k <= 2` each row is a dense string of `0`/`1` or
+//!   `.`/`#` (off/on). For `k > 2` each row is either a dense string of
+//!   decimal digits (only workable while `k <= 10`) or whitespace-separated
+//!   decimal numbers, letting each cell hold any value in `0..k`.
+
+use std::fmt;
+use ::bitmat::BitMat;
+use ::modmat::ModMat;
+
+/// A board parsed from text: either the classic binary case or a `Z_k`
+/// board for `k > 2`.
+pub enum Board {
+    Binary(BitMat),
+    Modular(ModMat),
+}
+
+/// A parsed puzzle: the board itself plus whether it should be solved on a
+/// toroidal (wrap-around) field.
+pub struct Puzzle {
+    pub board: Board,
+    pub wrap: bool,
+}
+
+/// Parses a puzzle from text in the format described in the module docs.
+///
+/// # Examples
+///
+/// ```
+/// use los::io::{parse, Board};
+///
+/// let puzzle = parse("3 3\n.#.\n###\n.#.\n").unwrap();
+/// match puzzle.board {
+///     Board::Binary(field) => assert_eq!(field.get(1, 0), true),
+///     Board::Modular(_) => panic!("expected a binary board"),
+/// }
+/// ```
+pub fn parse(text: &str) -> Result<Puzzle, String> {
+    let mut lines = text
+        .lines()
+        .filter(|line| !is_comment(line))
+        .filter(|line| !line.trim().is_empty());
+
+    let first = lines.next().ok_or_else(|| "empty puzzle".to_string())?;
+
+    let (n_rows, n_cols, k, wrap, first_is_header) = match parse_header(first) {
+        Some((n_rows, n_cols, k, wrap)) => (n_rows, n_cols, k, wrap, true),
+        None => (0, 0, 2, false, false),
+    };
+
+    let mut rows: Vec<&str> = Vec::new();
+    if !first_is_header {
+        rows.push(first);
+    }
+    rows.extend(lines);
+
+    if rows.is_empty() {
+        return Err("puzzle has no rows".to_string());
+    }
+
+    let n_rows = if first_is_header { n_rows } else { rows.len() };
+    let n_cols = if first_is_header {
+        n_cols
+    } else {
+        parse_row(rows[0], k).len()
+    };
+
+    if rows.len() != n_rows {
+        return Err(format!(
+            "expected {} rows, found {}",
+            n_rows,
+            rows.len()
+        ));
+    }
+
+    let board = if k <= 2 {
+        let mut field = BitMat::with_size(n_rows, n_cols);
+        for (r, line) in rows.iter().enumerate() {
+            let values = parse_row(line, k);
+            if values.len() != n_cols {
+                return Err(format!("row {} has {} cells, expected {}", r, values.len(), n_cols));
+            }
+            for (c, &value) in values.iter().enumerate() {
+                field.set(r, c, value != 0);
+            }
+        }
+        Board::Binary(field)
+    } else {
+        let mut field = ModMat::with_size(n_rows, n_cols, k as u32);
+        for (r, line) in rows.iter().enumerate() {
+            let values = parse_row(line, k);
+            if values.len() != n_cols {
+                return Err(format!("row {} has {} cells, expected {}", r, values.len(), n_cols));
+            }
+            for (c, &value) in values.iter().enumerate() {
+                field.set(r, c, value as u32);
+            }
+        }
+        Board::Modular(field)
+    };
+
+    Ok(Puzzle { board: board, wrap: wrap })
+}
+
+/// Writes a puzzle back out in the format `parse` accepts, including the
+/// header, so puzzles round-trip through `parse(&write(puzzle))`. Two cases
+/// do not: an empty board (`parse` rejects a puzzle with no rows), and a
+/// one-column k-state board holding a value of 10 or more (its lone number
+/// has no whitespace, so it is read back as a dense row of digits).
+pub fn write(puzzle: &Puzzle) -> String {
+    match puzzle.board {
+        Board::Binary(ref field) => {
+            let mut text = format!("{} {}", field.n_rows(), field.n_cols());
+            if puzzle.wrap {
+                text += " wrap=1";
+            }
+            text += "\n";
+            text += &field.to_string();
+            text
+        }
+        Board::Modular(ref field) => {
+            let mut text = format!(
+                "{} {} k={}",
+                field.n_rows(),
+                field.n_cols(),
+                field.modulus()
+            );
+            if puzzle.wrap {
+                text += " wrap=1";
+            }
+            text += "\n";
+            text += &field.to_string();
+            text
+        }
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", write(self))
+    }
+}
+
+// A line is a comment when it starts with "#" followed by whitespace (or is
+// just "#"), reserving a bare leading "#" glued to other characters for the
+// on-cell shorthand used by dense binary rows.
+fn is_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed == "#" || trimmed.starts_with("# ")
+}
+
+// Tries to read `line` as a header: `n_rows n_cols [k=K] [wrap=0|1]`.
+fn parse_header(line: &str) -> Option<(usize, usize, usize, bool)> {
+    let mut tokens = line.split_whitespace();
+
+    let n_rows = tokens.next()?.parse::<usize>().ok()?;
+    let n_cols = tokens.next()?.parse::<usize>().ok()?;
+    let mut k = 2usize;
+    let mut wrap = false;
+
+    for token in tokens {
+        if let Some(value) = token.strip_prefix_compat("k=") {
+            k = value.parse::<usize>().ok()?;
+        } else if let Some(value) = token.strip_prefix_compat("wrap=") {
+            wrap = value != "0";
+        } else {
+            return None;
+        }
+    }
+
+    Some((n_rows, n_cols, k, wrap))
+}
+
+// Parses a single data row into cell values in `0..k`. Whitespace-separated
+// rows support any `k`; dense rows (no whitespace) support `k <= 10`, with
+// `.`/`#` accepted as 0/1 shorthand when `k <= 2`.
+fn parse_row(line: &str, k: usize) -> Vec<usize> {
+    let trimmed = line.trim();
+
+    if trimmed.contains(char::is_whitespace) {
+        return trimmed
+            .split_whitespace()
+            .map(|tok| tok.parse::<usize>().unwrap_or(0) % k.max(1))
+            .collect();
+    }
+
+    trimmed
+        .chars()
+        .map(|c| match c {
+            '.' => 0,
+            '#' => 1,
+            d if d.is_digit(10) => (d.to_digit(10).unwrap() as usize) % k.max(1),
+            _ => 0,
+        })
+        .collect()
+}
+
+// `str::strip_prefix` was stabilized after this crate's baseline toolchain,
+// so this is a small self-contained stand-in.
+trait StripPrefixCompat {
+    fn strip_prefix_compat<'a>(&'a self, prefix: &str) -> Option<&'a str>;
+}
+
+impl StripPrefixCompat for str {
+    fn strip_prefix_compat<'a>(&'a self, prefix: &str) -> Option<&'a str> {
+        if self.starts_with(prefix) {
+            Some(&self[prefix.len()..])
+        } else {
+            None
+        }
+    }
+}
@@ -1,7 +1,11 @@
 pub mod bitvec;
+pub mod bitset;
 pub mod bitmat;
 pub mod bitalg;
+pub mod modmat;
+pub mod modalg;
 pub mod ligsol;
+pub mod io;
 
 #[cfg(test)]
 mod tests {
@@ -18,4 +22,174 @@ mod tests {
         mat[0].set(0, true);
         assert_eq!(mat[0].get(0), true);
     }
+
+    #[test]
+    fn bitgauss_window_path_matches_brute_force() {
+        use super::bitalg::BitGauss;
+        use super::bitmat::BitMat;
+
+        // `m4ri_window_size` picks k = 2 for n_rows in 4..=7, so n = 5 below
+        // drives two full windows (columns 0-1, then 2-3) plus a trailing
+        // window of size 1 -- exercising the k >= 2 path that the doctest in
+        // `BitGauss::gauss` (fixed at n = 3, k = 1) never reaches.
+        let n = 5;
+        let coeffs = [
+            [false, true, false, false, true],
+            [true, false, true, false, false],
+            [true, true, false, true, false],
+            [false, true, false, true, true],
+            [false, true, true, true, true],
+        ];
+        let rhs = [true, false, true, true, false];
+
+        let mut sys = BitMat::with_size(n, n + 1);
+        for row in 0..n {
+            for col in 0..n {
+                sys.set(row, col, coeffs[row][col]);
+            }
+            sys.set(row, n, rhs[row]);
+        }
+
+        let solution = BitGauss::with(sys)
+            .solve()
+            .expect("brute-forced system below is solvable");
+
+        for row in 0..n {
+            let mut acc = false;
+            for col in 0..n {
+                if coeffs[row][col] && solution.get(col) {
+                    acc = !acc;
+                }
+            }
+            assert_eq!(acc, rhs[row], "solution fails equation {}", row);
+        }
+
+        // Cross-check the weight against an exhaustive search over every
+        // assignment, since `solve` is specifically supposed to return a
+        // minimum-weight solution.
+        let mut best_weight = None;
+        for bits in 0..(1u32 << n) {
+            let assignment: Vec<bool> = (0..n).map(|i| (bits >> i) & 1 == 1).collect();
+
+            let satisfies = (0..n).all(|row| {
+                let mut acc = false;
+                for col in 0..n {
+                    if coeffs[row][col] && assignment[col] {
+                        acc = !acc;
+                    }
+                }
+                acc == rhs[row]
+            });
+
+            if satisfies {
+                let weight = assignment.iter().filter(|&&b| b).count();
+                best_weight = Some(best_weight.map_or(weight, |w: usize| w.min(weight)));
+            }
+        }
+
+        let expected_weight = best_weight.expect("brute force found no solution at all");
+        assert_eq!(solution.count_ones() as usize, expected_weight);
+    }
+
+    #[test]
+    fn bitgauss_skips_dependent_leading_column() {
+        use super::bitalg::BitGauss;
+        use super::bitmat::BitMat;
+
+        // Column 0 is all-zero, so it can never take a pivot and is the
+        // system's one true free variable -- not the contiguous tail
+        // `rank..n_vars` that a diagonal-pivot scheme would assume once row
+        // 2 (the sum of rows 0 and 1) drops rank to 2.
+        let n = 3;
+        let coeffs = [
+            [false, true, false],
+            [false, false, true],
+            [false, true, true],
+        ];
+        let rhs = [true, false, true];
+
+        let build_sys = || {
+            let mut sys = BitMat::with_size(n, n + 1);
+            for row in 0..n {
+                for col in 0..n {
+                    sys.set(row, col, coeffs[row][col]);
+                }
+                sys.set(row, n, rhs[row]);
+            }
+            sys
+        };
+
+        let mut expected: Vec<Vec<bool>> = Vec::new();
+        for bits in 0..(1u32 << n) {
+            let assignment: Vec<bool> = (0..n).map(|i| (bits >> i) & 1 == 1).collect();
+
+            let satisfies = (0..n).all(|row| {
+                let mut acc = false;
+                for col in 0..n {
+                    if coeffs[row][col] && assignment[col] {
+                        acc = !acc;
+                    }
+                }
+                acc == rhs[row]
+            });
+
+            if satisfies {
+                expected.push(assignment);
+            }
+        }
+
+        let (particular, basis) = BitGauss::with(build_sys())
+            .solution_space()
+            .expect("brute-forced system above is solvable");
+
+        assert_eq!(basis.len(), 1, "column 0 should be the only free variable");
+
+        let mut combined = particular.clone();
+        combined.xor_assign(&basis[0]);
+
+        let mut got: Vec<Vec<bool>> = vec![
+            (0..n).map(|i| particular.get(i)).collect(),
+            (0..n).map(|i| combined.get(i)).collect(),
+        ];
+        got.sort();
+        expected.sort();
+        assert_eq!(got, expected);
+
+        let solution = BitGauss::with(build_sys())
+            .solve()
+            .expect("brute-forced system above is solvable");
+        let min_weight = expected
+            .iter()
+            .map(|a| a.iter().filter(|&&b| b).count())
+            .min()
+            .unwrap();
+        assert_eq!(solution.count_ones() as usize, min_weight);
+    }
+
+    #[test]
+    fn bitmat_transpose_asymmetric() {
+        use super::bitvec::WORDSIZE;
+
+        // Non-square, larger than one WORDSIZE tile in both dimensions, and
+        // with no symmetry in the bit pattern, so a swapped tiling direction
+        // (or any other axis mix-up) can't hide behind an accidental match.
+        let n_rows = WORDSIZE + 3;
+        let n_cols = WORDSIZE + 5;
+        let mut mat = super::bitmat::BitMat::with_size(n_rows, n_cols);
+
+        for row in 0..n_rows {
+            mat.set(row, (row * 3 + 1) % n_cols, true);
+        }
+        mat.set(0, n_cols - 1, true);
+
+        let t = mat.transpose();
+        assert_eq!(t.n_rows(), n_cols);
+        assert_eq!(t.n_cols(), n_rows);
+
+        for row in 0..n_rows {
+            for col in 0..n_cols {
+                assert_eq!(t.get(col, row), mat.get(row, col));
+            }
+        }
+    }
 }
@@ -16,7 +16,10 @@
 //! Solves "Lights off" pazzle.
 
 use ::bitmat::BitMat;
-use ::bitalg::BitGauss;
+use ::bitvec::BitVec;
+use ::bitalg::{BitGauss, bitvec_increment, xor_row_into};
+use ::modmat::ModMat;
+use ::modalg::ModGauss;
 
 /// Solves "Lights Off" pazzle.
 pub struct LightsSolver {
@@ -28,55 +31,94 @@ pub struct LightsSolver {
 }
 
 impl LightsSolver {
-    /// Creates "Lights Off" solver with specified field.
+    /// Creates "Lights Off" solver with specified field, using the classic
+    /// plus-shaped von Neumann toggle rule on an open (non-wrapping) board:
+    /// pressing a cell toggles itself and its up/down/left/right neighbors.
     pub fn with(field: &BitMat) -> LightsSolver {
+        LightsSolver::with_rule(field, &[(0, 0), (0, 1), (0, -1), (1, 0), (-1, 0)], false)
+    }
+
+    /// Creates a "Lights Off" solver for a custom toggle neighborhood.
+    /// `offsets` lists the `(row, col)` deltas a press applies relative to
+    /// the pressed cell (e.g. the classic rule above, or a diagonal
+    /// "Lights Out 2000" rule). When `wrap` is `true` the board is treated
+    /// as toroidal, so offsets past an edge wrap around to the other side
+    /// instead of falling outside the field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::bitmat::BitMat;
+    /// use los::ligsol::LightsSolver;
+    ///
+    /// let field = BitMat::with_size(3, 3);
+    /// let diagonal = [(0, 0), (1, 1), (1, -1), (-1, 1), (-1, -1)];
+    /// let mut solver = LightsSolver::with_rule(&field, &diagonal, true);
+    /// assert!(solver.solve().is_some());
+    /// ```
+    pub fn with_rule(field: &BitMat, offsets: &[(isize, isize)], wrap: bool) -> LightsSolver {
         let n_rows = field.n_rows() as isize;
         let n_cols = field.n_cols() as isize;
-        let n = n_rows * n_cols;
-        let mut himself: isize;
-        let mut neighbor: isize;
-        let mut sys = BitMat::with_size(n as usize, (n + 1) as usize);
+        let n = (n_rows * n_cols) as usize;
+        let mut toggle = BitMat::with_size(n, n);
 
         for row in 0..n_rows {
             for col in 0..n_cols {
-                himself = sys_index(row, col, n_rows, n_cols);
-                sys.set(himself as usize, himself as usize, true);
+                let himself = (n_cols * row + col) as usize;
 
-                neighbor = sys_index(row, col + 1, n_rows, n_cols);
-                if neighbor >= 0 {
-                    sys.set(himself as usize, neighbor as usize, true);
+                for &(dr, dc) in offsets {
+                    if let Some(target) = sys_index(row + dr, col + dc, n_rows, n_cols, wrap) {
+                        // Accumulate by parity: on a small wrapped board two
+                        // offsets can land on the same `target` (e.g. `(0, 1)`
+                        // and `(0, -1)` both mapping to the lone other column
+                        // when `n_cols == 2`), and pressing `himself` should
+                        // toggle it twice, i.e. not at all.
+                        let toggled = !toggle.get(target, himself);
+                        toggle.set(target, himself, toggled);
+                    }
                 }
+            }
+        }
 
-                neighbor = sys_index(row, col - 1, n_rows, n_cols);
-                if neighbor >= 0 {
-                    sys.set(himself as usize, neighbor as usize, true);
-                }
+        LightsSolver::from_toggle_matrix(field, &toggle)
+    }
 
-                neighbor = sys_index(row + 1, col, n_rows, n_cols);
-                if neighbor >= 0 {
-                    sys.set(neighbor as usize, himself as usize, true);
-                }
+    /// Creates a "Lights Off" solver from a precomputed `n x n` toggle
+    /// (adjacency) matrix, where `n = field.n_rows() * field.n_cols()` and
+    /// `toggle.get(i, j)` means pressing cell `j` toggles cell `i`. This is
+    /// the lowest-level constructor: `with` and `with_rule` both build a
+    /// toggle matrix from a neighborhood rule and delegate here.
+    pub fn from_toggle_matrix(field: &BitMat, toggle: &BitMat) -> LightsSolver {
+        let n_rows = field.n_rows();
+        let n_cols = field.n_cols();
+        let n = n_rows * n_cols;
+        let mut sys = BitMat::with_size(n, n + 1);
 
-                neighbor = sys_index(row - 1, col, n_rows, n_cols);
-                if neighbor >= 0 {
-                    sys.set(neighbor as usize, himself as usize, true);
+        for i in 0..n {
+            for j in 0..n {
+                if toggle.get(i, j) {
+                    sys.set(i, j, true);
                 }
+            }
+        }
 
-                sys.set(himself as usize, n as usize, field.get(row as usize, col as usize));
+        for row in 0..n_rows {
+            for col in 0..n_cols {
+                sys.set(n_cols * row + col, n, field.get(row, col));
             }
         }
 
         let alg = BitGauss::with(sys);
-        
+
         LightsSolver {
             alg: alg,
-            n_rows: n_rows as usize,
-            n_cols: n_cols as usize,
+            n_rows: n_rows,
+            n_cols: n_cols,
             n_solutions: 0,
             min_weight: 0,
         }
     }
-        
+
     /// Returns an immutable `n_solutions`.
     #[inline]
     pub fn n_solutions(&self) -> usize {
@@ -96,29 +138,213 @@ impl LightsSolver {
                 self.n_solutions = 1usize << (self.n_rows * self.n_cols - self.alg.rank());
                 self.min_weight = syssol.count_ones() as usize;
 
-                let mut sol = BitMat::with_size(self.n_rows, self.n_cols);
-                for row in 0..self.n_rows {
-                    for col in 0..self.n_cols {
-                        if syssol.get(self.n_cols * row + col) {
-                            sol.set(row, col, true);
-                        }
-                    }
-                }
-
-                Some(sol)
+                Some(board_from_vars(&syssol, self.n_rows, self.n_cols))
             },
             None => None
         }
     }
+
+    /// Returns an iterator over every valid press-pattern board, walking the
+    /// affine solution space in Gray-code order so each board differs from
+    /// the previous one by exactly one null-space basis vector (one free
+    /// press plus whatever pivot presses it forces). Yields nothing if the
+    /// puzzle has no solution.
+    pub fn solutions(&mut self) -> Solutions {
+        match self.alg.solution_space() {
+            Some((particular, basis)) => {
+                self.n_solutions = 1usize << basis.len();
+
+                let counter = BitVec::with_length(basis.len());
+                Solutions {
+                    n_rows: self.n_rows,
+                    n_cols: self.n_cols,
+                    current: particular,
+                    basis: basis,
+                    counter: counter,
+                    done: false,
+                    started: false,
+                }
+            },
+            None => Solutions::empty(self.n_rows, self.n_cols),
+        }
+    }
+}
+
+/// Iterator over every valid press-pattern board of a `LightsSolver`,
+/// returned by `LightsSolver::solutions`.
+pub struct Solutions {
+    n_rows: usize,
+    n_cols: usize,
+    current: BitVec,
+    basis: Vec<BitVec>,
+    counter: BitVec,
+    done: bool,
+    started: bool,
+}
+
+impl Solutions {
+    fn empty(n_rows: usize, n_cols: usize) -> Solutions {
+        Solutions {
+            n_rows: n_rows,
+            n_cols: n_cols,
+            current: BitVec::new(),
+            basis: vec![],
+            counter: BitVec::new(),
+            done: true,
+            started: true,
+        }
+    }
 }
 
-/// Calculates of the index in the system matrix by row and column in the field.
-/// Returns -1 if out of field range.
+impl Iterator for Solutions {
+    type Item = BitMat;
+
+    fn next(&mut self) -> Option<BitMat> {
+        if self.done {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+        } else {
+            match bitvec_increment(&mut self.counter) {
+                Some(bit) => xor_row_into(&mut self.current, &self.basis[bit]),
+                None => {
+                    self.done = true;
+                    return None;
+                }
+            }
+        }
+
+        Some(board_from_vars(&self.current, self.n_rows, self.n_cols))
+    }
+}
+
+/// Maps a flat system solution (one bit per field cell, row-major) back to
+/// a board.
+fn board_from_vars(vars: &BitVec, n_rows: usize, n_cols: usize) -> BitMat {
+    let mut board = BitMat::with_size(n_rows, n_cols);
+
+    for row in 0..n_rows {
+        for col in 0..n_cols {
+            if vars.get(n_cols * row + col) {
+                board.set(row, col, true);
+            }
+        }
+    }
+
+    board
+}
+
+/// Calculates the index in the system matrix by row and column in the
+/// field. When `wrap` is `true`, out-of-range coordinates are wrapped
+/// toroidally; otherwise returns `None` if out of field range.
 #[inline]
-fn sys_index(row: isize, col: isize, n_rows: isize, n_cols: isize) -> isize {
-    if 0 <= row && row < n_rows as isize && 0 <= col && col < n_cols as isize {
-        n_cols * row + col
+fn sys_index(row: isize, col: isize, n_rows: isize, n_cols: isize, wrap: bool) -> Option<usize> {
+    let (row, col) = if wrap {
+        (row.rem_euclid(n_rows), col.rem_euclid(n_cols))
+    } else {
+        (row, col)
+    };
+
+    if 0 <= row && row < n_rows && 0 <= col && col < n_cols {
+        Some((n_cols * row + col) as usize)
     } else {
-        -1
+        None
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// k-state ("Z_k") variant
+////////////////////////////////////////////////////////////////////////////////
+
+/// Solves the `Z_k` generalization of "Lights Off", where each press
+/// advances a cell and its plus-shaped neighbors by `1 (mod k)` instead of
+/// merely toggling them. The board is always open: neighbors past an edge
+/// are not wrapped around.
+pub struct ModLightsSolver {
+    alg: ModGauss,
+    n_rows: usize,
+    n_cols: usize,
+    k: u32,
+    n_solutions: usize,
+    min_weight: usize,
+}
+
+impl ModLightsSolver {
+    /// Creates a `Z_k` "Lights Off" solver with specified field. `field`'s
+    /// modulus (`field.modulus()`) is the number of states `k` each cell
+    /// cycles through.
+    pub fn with(field: &ModMat) -> ModLightsSolver {
+        let n_rows = field.n_rows() as isize;
+        let n_cols = field.n_cols() as isize;
+        let k = field.modulus();
+        let n = (n_rows * n_cols) as usize;
+        let mut sys = ModMat::with_size(n, n + 1, k);
+
+        let offsets = [(0, 0), (0, 1), (0, -1), (1, 0), (-1, 0)];
+
+        for row in 0..n_rows {
+            for col in 0..n_cols {
+                let himself = (n_cols * row + col) as usize;
+
+                for &(dr, dc) in &offsets {
+                    if let Some(target) = sys_index(row + dr, col + dc, n_rows, n_cols, false) {
+                        let current = sys.get(target, himself);
+                        sys.set(target, himself, current + 1);
+                    }
+                }
+
+                // To drive `himself` from its current state down to 0, the
+                // presses applied to it must sum to the negation of that
+                // state (mod k), not the state itself.
+                let current = field.get(row as usize, col as usize);
+                sys.set(himself, n, (k - current) % k);
+            }
+        }
+
+        let alg = ModGauss::with(sys);
+
+        ModLightsSolver {
+            alg: alg,
+            n_rows: n_rows as usize,
+            n_cols: n_cols as usize,
+            k: k,
+            n_solutions: 0,
+            min_weight: 0,
+        }
+    }
+
+    /// Returns an immutable `n_solutions`.
+    #[inline]
+    pub fn n_solutions(&self) -> usize {
+        self.n_solutions
+    }
+
+    /// Returns an immutable `min_weight`.
+    #[inline]
+    pub fn min_weight(&self) -> usize {
+        self.min_weight
+    }
+
+    /// Finds a minimal total-press-count solution in the system.
+    pub fn solve(&mut self) -> Option<ModMat> {
+        match self.alg.solve() {
+            Some(vars) => {
+                self.n_solutions = (self.k as usize)
+                    .pow((self.n_rows * self.n_cols - self.alg.rank()) as u32);
+                self.min_weight = vars.iter().map(|&v| v as usize).sum();
+
+                let mut board = ModMat::with_size(self.n_rows, self.n_cols, self.k);
+                for row in 0..self.n_rows {
+                    for col in 0..self.n_cols {
+                        board.set(row, col, vars[self.n_cols * row + col]);
+                    }
+                }
+
+                Some(board)
+            },
+            None => None
+        }
     }
 }
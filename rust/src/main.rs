@@ -16,28 +16,107 @@
 extern crate los;
 
 use std::env;
+use std::fs::File;
+use std::io::{self, Read};
 use std::time::SystemTime;
 use los::bitmat::BitMat;
-use los::ligsol::LightsSolver;
+use los::ligsol::{LightsSolver, ModLightsSolver};
+use los::io::{parse, Board};
 
 fn main() {
-    let n = env::args().nth(1).unwrap_or("10".to_string()).parse::<usize>().unwrap_or(10);
+    let arg = env::args().nth(1);
+
+    match arg.as_ref().and_then(|s| s.parse::<usize>().ok()) {
+        Some(n) => solve_all_on(n),
+        None => solve_from_input(arg.as_ref().map(|s| s.as_str())),
+    }
+}
+
+// `los <size>`: solves a synthetic all-on square board, as before.
+fn solve_all_on(n: usize) {
     let mut field = BitMat::with_size(n, n);
-    
+
     for i in 0..n {
         field[i].setall(true);
     }
 
-    println!("Usage: los <size>\nSolving {} x {}...\n", n, n);
+    println!("Usage: los <size|file>\nSolving {} x {}...\n", n, n);
 
-    let mut solver = LightsSolver::from(&field);
-    let now = SystemTime::now();
+    let mut solver = LightsSolver::with(&field);
+    time(|| print_binary_result(&mut solver));
+}
+
+// `los <file>` (or `los -`/no argument for stdin): loads a puzzle in the
+// text format parsed by `los::io`.
+fn solve_from_input(path: Option<&str>) {
+    let text = match read_input(path) {
+        Ok(text) => text,
+        Err(e) => {
+            println!("Error reading puzzle: {}", e);
+            return;
+        }
+    };
+
+    let puzzle = match parse(&text) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            println!("Error parsing puzzle: {}", e);
+            return;
+        }
+    };
+
+    println!("Usage: los <size|file>\nSolving loaded puzzle...\n");
+
+    match puzzle.board {
+        Board::Binary(field) => {
+            let offsets = [(0, 0), (0, 1), (0, -1), (1, 0), (-1, 0)];
+            let mut solver = LightsSolver::with_rule(&field, &offsets, puzzle.wrap);
+            time(|| print_binary_result(&mut solver));
+        }
+        Board::Modular(field) => {
+            // `ModLightsSolver` only builds open boards, so `puzzle.wrap` is
+            // not applied to k-state puzzles.
+            let mut solver = ModLightsSolver::with(&field);
+            time(|| print_modular_result(&mut solver));
+        }
+    }
+}
 
+fn read_input(path: Option<&str>) -> Result<String, String> {
+    let mut text = String::new();
+
+    match path {
+        None | Some("-") => {
+            io::stdin().read_to_string(&mut text).map_err(|e| e.to_string())?;
+        }
+        Some(path) => {
+            let mut file = File::open(path).map_err(|e| e.to_string())?;
+            file.read_to_string(&mut text).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(text)
+}
+
+fn print_binary_result(solver: &mut LightsSolver) {
     match solver.solve() {
-        Some(sol) => println!("{}\nNumber of solutions: {}\nMinimal weight: {}", 
-                              sol, solver.alg().n_solutions(), solver.alg().min_weight()),
+        Some(sol) => println!("{}\nNumber of solutions: {}\nMinimal weight: {}",
+                              sol, solver.n_solutions(), solver.min_weight()),
         None => println!("No solution"),
     }
+}
+
+fn print_modular_result(solver: &mut ModLightsSolver) {
+    match solver.solve() {
+        Some(sol) => println!("{}\nNumber of solutions: {}\nMinimal weight: {}",
+                              sol, solver.n_solutions(), solver.min_weight()),
+        None => println!("No solution"),
+    }
+}
+
+fn time<F: FnOnce()>(f: F) {
+    let now = SystemTime::now();
+    f();
 
     match now.elapsed() {
        Ok(elapsed) => {
@@ -46,5 +125,5 @@ fn main() {
        Err(e) => {
            println!("Error: {:?}", e);
        }
-   }    
+   }
 }
@@ -0,0 +1,267 @@
+// Copyright (C) 2017 - Pavel Nikitin
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Implements Gauss algorithm over `Z_k`, the integers modulo `k`, for
+//! `n_rows` equations and `n_cols-1` variables. This is the multi-state
+//! counterpart of `bitalg::BitGauss`.
+//!
+//! When `k` is prime every nonzero element is invertible, so elimination is
+//! a direct mirror of the GF(2) case using modular inverses instead of XOR.
+//! When `k` is composite, a column pivot is found via the extended
+//! Euclidean algorithm: rows sharing a nonzero entry in that column are
+//! combined pairwise (an integer-unimodular transform, so it never changes
+//! the row space) until a single row remains, whose entry is the gcd of the
+//! column. If that gcd happens to be coprime to `k` the column still clears
+//! exactly, otherwise only the part of the column that is a multiple of the
+//! gcd clears -- a full Smith-normal-form reduction would also need column
+//! operations to handle the remainder, which is out of scope here.
+
+use ::modmat::ModMat;
+
+/// Implements Gauss algorithm over `Z_k` for `n_rows` equations and
+/// `n_cols-1` variables.
+pub struct ModGauss {
+    sys: ModMat,
+    rank: usize,
+    // (column, pivot value) for each pivot found, in the order pivot rows
+    // were assigned, so pivots[r] describes row r of `sys`.
+    pivots: Vec<(usize, u32)>,
+}
+
+impl ModGauss {
+    /// Creates a modular Gauss algorithm with the specified system.
+    #[inline]
+    pub fn with(sys: ModMat) -> ModGauss {
+        ModGauss {
+            sys: sys,
+            rank: 0,
+            pivots: Vec::new(),
+        }
+    }
+
+    /// Returns an immutable `sys`.
+    #[inline]
+    pub fn sys(&self) -> &ModMat {
+        &self.sys
+    }
+
+    /// Returns a mutable `sys`.
+    #[inline]
+    pub fn sys_mut(&mut self) -> &mut ModMat {
+        &mut self.sys
+    }
+
+    /// Returns a `rank` of system.
+    #[inline]
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Gausses the system with `n_rows` equations over `Z_k` and
+    /// `n_cols-1` variables.
+    pub fn gauss(&mut self) {
+        let n_rows = self.sys.n_rows();
+        let k = self.sys.modulus();
+        self.rank = 0;
+        self.pivots.clear();
+
+        for col in 0..n_rows {
+            // Reduce every candidate row with a nonzero entry in this
+            // column down to a single one via pairwise gcd combination.
+            loop {
+                let nonzero: Vec<usize> = (self.rank..n_rows)
+                    .filter(|&j| self.sys.get(j, col) != 0)
+                    .collect();
+
+                if nonzero.len() < 2 {
+                    break;
+                }
+
+                let (r1, r2) = (nonzero[0], nonzero[1]);
+                let a = self.sys.get(r1, col) as i64;
+                let b = self.sys.get(r2, col) as i64;
+                let (g, x, y) = extended_gcd(a, b);
+
+                self.sys.combine_rows(r1, r2, x, y, -(b / g), a / g);
+            }
+
+            let pivot_row = (self.rank..n_rows).find(|&j| self.sys.get(j, col) != 0);
+
+            let pivot_row = match pivot_row {
+                Some(r) => r,
+                None => continue,
+            };
+
+            self.sys.swap(pivot_row, self.rank);
+            let pivot_val = self.sys.get(self.rank, col);
+
+            self.eliminate_column(col, pivot_val, k);
+            self.pivots.push((col, pivot_val));
+            self.rank += 1;
+        }
+    }
+
+    // Clears `col` from every row other than the pivot row, using the
+    // modular inverse of `pivot_val` when `gcd(pivot_val, k) == 1`, or the
+    // best divisibility-respecting approximation otherwise.
+    fn eliminate_column(&mut self, col: usize, pivot_val: u32, k: u32) {
+        let n_rows = self.sys.n_rows();
+
+        for j in 0..n_rows {
+            if j == self.rank {
+                continue;
+            }
+
+            let value = self.sys.get(j, col);
+            if value == 0 {
+                continue;
+            }
+
+            let factor = divide_mod(value, pivot_val, k);
+            self.sys.add_scaled_row(j, self.rank, (k - factor) % k);
+        }
+    }
+
+    /// Finds a solution of minimal total press count (the sum of the
+    /// variable values, rather than the Hamming weight used over GF(2)).
+    pub fn solve(&mut self) -> Option<Vec<u32>> {
+        self.gauss();
+
+        let n_rows = self.sys.n_rows();
+        let n_cols = self.sys.n_cols();
+        let n_vars = n_cols - 1;
+        let rank = self.rank;
+        let k = self.sys.modulus();
+
+        for i in rank..n_rows {
+            if self.sys.get(i, n_vars) != 0 {
+                return None;
+            }
+        }
+
+        let pivot_cols: Vec<usize> = self.pivots.iter().map(|&(c, _)| c).collect();
+        let free_cols: Vec<usize> = (0..n_vars).filter(|c| !pivot_cols.contains(c)).collect();
+        let n_rest = free_cols.len();
+
+        let mut solution = vec![0u32; n_vars];
+        let mut min_weight = u64::max_value();
+        let mut assignment = vec![0u32; n_rest];
+
+        loop {
+            let mut candidate = vec![0u32; n_vars];
+
+            for (idx, &f) in free_cols.iter().enumerate() {
+                candidate[f] = assignment[idx];
+            }
+
+            for (row, &(col, pivot_val)) in self.pivots.iter().enumerate() {
+                let mut rhs = self.sys.get(row, n_vars) as i64;
+
+                for (idx, &f) in free_cols.iter().enumerate() {
+                    let coeff = self.sys.get(row, f) as i64;
+                    rhs -= coeff * assignment[idx] as i64;
+                }
+
+                let rhs = mod_pos(rhs, k as i64) as u32;
+                candidate[col] = divide_mod(rhs, pivot_val, k);
+            }
+
+            let weight: u64 = candidate.iter().map(|&v| v as u64).sum();
+
+            if weight < min_weight {
+                min_weight = weight;
+                solution = candidate;
+            }
+
+            if !odometer_increment(&mut assignment, k) {
+                break;
+            }
+        }
+
+        Some(solution)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions for modular row reduction
+////////////////////////////////////////////////////////////////////////////////
+
+// Extended Euclidean algorithm: returns `(g, x, y)` with `g = gcd(a, b)` and
+// `x*a + y*b == g`. Assumes `a, b > 0`.
+fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x1, y1) = extended_gcd(b, a % b);
+        (g, y1, x1 - (a / b) * y1)
+    }
+}
+
+// Reduces `value` into the range `0..modulus`, handling negative `value`.
+#[inline]
+fn mod_pos(value: i64, modulus: i64) -> i64 {
+    ((value % modulus) + modulus) % modulus
+}
+
+// Finds `x` in `0..k` with `pivot * x === value (mod k)`. When
+// `gcd(pivot, k) == 1` this is exact. When `pivot` and `k` share a factor
+// `d`, an exact `x` only exists if `d` divides `value`; this still returns
+// its best approximation (solving the reduced `mod k/d` congruence) even
+// when it does not, since the caller has no way to signal a partial clear.
+fn divide_mod(value: u32, pivot: u32, k: u32) -> u32 {
+    if pivot == 0 {
+        return 0;
+    }
+
+    let (g, x, _) = extended_gcd(pivot as i64, k as i64);
+
+    if g == 1 {
+        let inv = mod_pos(x, k as i64);
+        return (mod_pos(inv * value as i64, k as i64)) as u32;
+    }
+
+    let g = g as u32;
+    let reduced_k = k / g;
+    let reduced_pivot = pivot / g;
+    let reduced_value = (value / g) as i64;
+
+    if reduced_pivot == 0 || reduced_k <= 1 {
+        return 0;
+    }
+
+    let (g2, x2, _) = extended_gcd(reduced_pivot as i64, reduced_k as i64);
+    if g2 != 1 {
+        // No exact solution is representable with row operations alone;
+        // fall back to zero rather than fabricating a wrong nonzero value.
+        return 0;
+    }
+
+    let inv = mod_pos(x2, reduced_k as i64);
+    (mod_pos(inv * reduced_value, reduced_k as i64)) as u32
+}
+
+// Increments a mixed-radix counter (every digit in `0..base`) by one.
+// Returns `false` once it has wrapped back around to all zeroes.
+fn odometer_increment(digits: &mut [u32], base: u32) -> bool {
+    for digit in digits.iter_mut() {
+        *digit += 1;
+        if *digit < base {
+            return true;
+        }
+        *digit = 0;
+    }
+
+    false
+}
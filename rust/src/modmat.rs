@@ -0,0 +1,151 @@
+// Copyright (C) 2017 - Pavel Nikitin
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! A contiguous growable matrix type over `Z_k`, the integers modulo `k`.
+//! This is the multi-state counterpart of `bitmat::BitMat`: instead of a
+//! single bit, each cell holds a value in `0..k`.
+
+use std::fmt;
+
+/// A contiguous growable matrix type over `Z_k`. Each cell holds a value in
+/// `0..k`.
+pub struct ModMat {
+    modulus: u32,
+    rows: Vec<Vec<u32>>,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Inherent methods
+////////////////////////////////////////////////////////////////////////////////
+
+impl ModMat {
+    /// Constructs a new `ModMat` with the specified `n_rows` number of rows,
+    /// each row holding `n_cols` elements, all initialized to `0`. Every
+    /// value stored in the matrix is taken modulo `modulus`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use los::modmat::ModMat;
+    ///
+    /// let mat = ModMat::with_size(3, 3, 5);
+    /// assert_eq!(mat.n_rows(), 3);
+    /// assert_eq!(mat.n_cols(), 3);
+    /// assert_eq!(mat.modulus(), 5);
+    /// ```
+    #[inline]
+    pub fn with_size(n_rows: usize, n_cols: usize, modulus: u32) -> ModMat {
+        ModMat {
+            modulus: modulus,
+            rows: vec![vec![0u32; n_cols]; n_rows],
+        }
+    }
+
+    /// Returns the modulus `k` values in this matrix are taken over.
+    #[inline]
+    pub fn modulus(&self) -> u32 {
+        self.modulus
+    }
+
+    /// Returns number of rows.
+    #[inline]
+    pub fn n_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns number of columns.
+    #[inline]
+    pub fn n_cols(&self) -> usize {
+        match self.n_rows() {
+            0 => 0,
+            _ => self.rows[0].len(),
+        }
+    }
+
+    /// Gets value by index `row` and index `col`.
+    #[inline]
+    pub fn get(&self, row: usize, col: usize) -> u32 {
+        self.rows[row][col]
+    }
+
+    /// Sets `value` (reduced modulo `modulus`) by index `row` and index `col`.
+    #[inline]
+    pub fn set(&mut self, row: usize, col: usize, value: u32) {
+        self.rows[row][col] = value % self.modulus;
+    }
+
+    /// Swaps in-place row by index `row_i` with row by index `row_j`.
+    #[inline]
+    pub fn swap(&mut self, row_i: usize, row_j: usize) {
+        self.rows.swap(row_i, row_j);
+    }
+
+    /// Replaces `row_i` and `row_j` in-place with the unimodular combination
+    /// `row_i' = x*row_i + y*row_j` and `row_j' = u*row_i + v*row_j` (all
+    /// modulo `modulus`), where `x*v - y*u == 1`. Because the transform is
+    /// unimodular it never changes the row space the two rows span, which is
+    /// exactly what `ModGauss` relies on when it combines rows via the
+    /// extended Euclidean algorithm to find a gcd pivot.
+    pub fn combine_rows(&mut self, row_i: usize, row_j: usize, x: i64, y: i64, u: i64, v: i64) {
+        let k = self.modulus as i64;
+        let n_cols = self.n_cols();
+
+        for c in 0..n_cols {
+            let a = self.rows[row_i][c] as i64;
+            let b = self.rows[row_j][c] as i64;
+
+            let new_i = mod_pos(x * a + y * b, k) as u32;
+            let new_j = mod_pos(u * a + v * b, k) as u32;
+
+            self.rows[row_i][c] = new_i;
+            self.rows[row_j][c] = new_j;
+        }
+    }
+
+    /// Adds `scalar * row_j` into `row_i`, in place, modulo `modulus`.
+    pub fn add_scaled_row(&mut self, row_i: usize, row_j: usize, scalar: u32) {
+        let k = self.modulus;
+        let n_cols = self.n_cols();
+
+        for c in 0..n_cols {
+            let value = self.rows[row_j][c];
+            self.rows[row_i][c] = (self.rows[row_i][c] + scalar * value) % k;
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Common trait implementations for ModMat
+////////////////////////////////////////////////////////////////////////////////
+
+impl fmt::Display for ModMat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut strmat = String::new();
+
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+            strmat += &cells.join(" ");
+            strmat += "\n";
+        }
+
+        write!(f, "{}", strmat)
+    }
+}
+
+// Reduces `value` into the range `0..modulus`, handling negative `value`.
+#[inline]
+fn mod_pos(value: i64, modulus: i64) -> i64 {
+    ((value % modulus) + modulus) % modulus
+}